@@ -0,0 +1,41 @@
+use crate::process::thread_manager;
+use crate::syscall::{SysResult, Syscall};
+
+impl Syscall<'_> {
+    /// Creates a child process and its initial thread as a copy of the
+    /// caller (`fork`/`clone`). Address-space duplication, pid allocation,
+    /// and scheduler registration are handled by `Process::fork` and
+    /// `ThreadManager::fork_thread`; this wires in the POSIX signal-state
+    /// half of fork that [`crate::process::Process::inherit_signal_state`]
+    /// added: the child's dispositions come from the parent process, its
+    /// `sig_mask` from the forking thread, and its pending-signal sets
+    /// start out empty rather than carrying over anything the parent had
+    /// queued.
+    pub fn sys_fork(&mut self) -> SysResult {
+        let child_process = self.process().fork();
+        child_process.lock().inherit_signal_state(&self.process());
+        let child_thread = thread_manager().fork_thread(self.thread, &child_process);
+        child_thread.lock().inherit_signal_state(self.thread);
+        let pid = child_process.lock().pid;
+        Ok(pid)
+    }
+
+    /// Replaces the calling process's image (`execve`). Loading the new
+    /// image is handled by the loader/VM subsystem; this wires in the
+    /// POSIX signal reset that
+    /// [`crate::process::Process::reset_signal_state_on_exec`] added:
+    /// dispositions set to a handler revert to `SIG_DFL` (`SIG_IGN` is
+    /// preserved), the alternate signal stack is disabled, and the
+    /// blocked mask — which lives on the thread, not the process — is
+    /// left untouched.
+    pub fn sys_execve(
+        &mut self,
+        path: *const u8,
+        argv: *const *const u8,
+        envp: *const *const u8,
+    ) -> SysResult {
+        self.vm().load_image(path, argv, envp)?;
+        self.process().reset_signal_state_on_exec();
+        Ok(0)
+    }
+}
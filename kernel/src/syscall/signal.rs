@@ -1,13 +1,284 @@
-use crate::process::{current_thread, process_of, thread_manager, PROCESSES};
+use crate::arch::timer::timer_now;
+use crate::process::{current_thread, process_of, thread_manager, Process, Thread, PROCESSES};
 use crate::process::{process, process_group};
 use crate::signal::Signal::SIGINT;
 use crate::signal::*;
-use crate::syscall::SysError::{EINVAL, ENOMEM, EPERM, ESRCH};
-use crate::syscall::{SysResult, Syscall};
+use crate::syscall::SysError::{EAGAIN, EINTR, EINVAL, ENOMEM, EPERM, ESRCH};
+use crate::syscall::{SysResult, Syscall, TimeSpec};
 use crate::thread;
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::sync::{Arc, Weak};
 use num::FromPrimitive;
+use spin::Mutex;
+
+/// First and last real-time signal numbers (inclusive), matching the
+/// `SIGRTMIN..=SIGRTMAX` range Linux exposes to userspace.
+const SIGRTMIN: usize = 34;
+const SIGRTMAX: usize = 64;
+
+/// Signals that have been raised for a thread or process but not yet
+/// delivered to a handler or a waiter, keyed by signal number.
+///
+/// Standard signals coalesce: at most one instance may be pending per
+/// number. Real-time signals (`SIGRTMIN..=SIGRTMAX`) instead queue in FIFO
+/// order, so N sends produce N deliveries.
+#[derive(Default)]
+pub struct PendingSignals {
+    set: Sigset,
+    info: BTreeMap<usize, Siginfo>,
+    realtime: BTreeMap<usize, VecDeque<Siginfo>>,
+}
+
+impl PendingSignals {
+    pub fn raise(&mut self, info: Siginfo) {
+        let signum = info.signo as usize;
+        self.set.add(signum);
+        if (SIGRTMIN..=SIGRTMAX).contains(&signum) {
+            self.realtime.entry(signum).or_default().push_back(info);
+        } else {
+            self.info.insert(signum, info);
+        }
+    }
+
+    /// Returns the lowest-numbered pending signal number that is a member
+    /// of `wanted`, without consuming it.
+    pub fn lowest(&self, wanted: &Sigset) -> Option<usize> {
+        (1..=64usize).find(|&i| self.set.contains(i) && wanted.contains(i))
+    }
+
+    /// Removes and returns the lowest-numbered pending signal that is a
+    /// member of `wanted`, preferring the oldest queued instance for
+    /// real-time signals.
+    pub fn take(&mut self, wanted: &Sigset) -> Option<Siginfo> {
+        let signum = self.lowest(wanted)?;
+        if (SIGRTMIN..=SIGRTMAX).contains(&signum) {
+            let queue = self.realtime.get_mut(&signum)?;
+            let info = queue.pop_front();
+            if queue.is_empty() {
+                self.set.remove(signum);
+            }
+            info
+        } else {
+            self.set.remove(signum);
+            self.info.remove(&signum)
+        }
+    }
+
+    fn contains_any_outside(&self, wanted: &Sigset, blocked: &Sigset) -> bool {
+        (1..=64usize).any(|i| self.set.contains(i) && !wanted.contains(i) && !blocked.contains(i))
+    }
+
+    fn pending_set(&self) -> Sigset {
+        self.set
+    }
+}
+
+/// `SignalAction.handler` value meaning "default action" (`SIG_DFL`).
+const SIG_DFL: usize = 0;
+/// `SignalAction.handler` value meaning "ignore" (`SIG_IGN`).
+const SIG_IGN: usize = 1;
+
+impl Process {
+    /// Resets signal disposition state for `execve`, per POSIX: every
+    /// disposition currently set to a user handler reverts to `SIG_DFL`,
+    /// dispositions left at `SIG_IGN` are preserved, and the alternate
+    /// signal stack is disabled. The blocked mask lives on the thread and
+    /// is untouched here.
+    pub fn reset_signal_state_on_exec(&mut self) {
+        for action in self.dispositions.iter_mut() {
+            if action.handler != SIG_DFL && action.handler != SIG_IGN {
+                *action = SignalAction::default();
+            }
+        }
+        self.sigaltstack = SignalStack::default();
+    }
+
+    /// Initializes this (child) process's signal state at fork/clone time:
+    /// `dispositions` are inherited verbatim from `parent`, and pending
+    /// signals start out empty regardless of what was pending there.
+    pub fn inherit_signal_state(&mut self, parent: &Process) {
+        self.dispositions = parent.dispositions;
+        self.pending = PendingSignals::default();
+    }
+}
+
+impl Thread {
+    /// Initializes this (child) thread's signal state at fork/clone time:
+    /// the blocked `sig_mask` is inherited from `parent`, and pending
+    /// signals start out empty.
+    pub fn inherit_signal_state(&mut self, parent: &Thread) {
+        self.sig_mask = parent.sig_mask;
+        self.pending = PendingSignals::default();
+    }
+}
+
+/// Whether a process with the given real/effective uid and session id may
+/// send `signal` to `target`, per the POSIX `kill` permission rule: the
+/// sender must be privileged, or its real/effective uid must match the
+/// target's real or saved set-user-ID. `SIGCONT` is always allowed within
+/// the same session regardless of uid.
+fn may_signal(
+    sender_ruid: usize,
+    sender_euid: usize,
+    sender_sid: usize,
+    signal: Signal,
+    target: &Process,
+) -> bool {
+    const ROOT_UID: usize = 0;
+    if sender_euid == ROOT_UID {
+        return true;
+    }
+    if signal == Signal::SIGCONT && sender_sid == target.sid {
+        return true;
+    }
+    sender_ruid == target.ruid
+        || sender_ruid == target.suid
+        || sender_euid == target.ruid
+        || sender_euid == target.suid
+}
 
 impl Syscall<'_> {
+    /// Raises `info` on `process` (or, when `tid >= 0`, the specific thread
+    /// within it).
+    ///
+    /// Per POSIX, a signal that is currently blocked by its target is
+    /// recorded in [`PendingSignals`] and left there until something
+    /// unblocks it (see [`Self::sys_rt_sigprocmask`]) or explicitly
+    /// consumes it (`sys_rt_sigtimedwait`); it never reaches a handler
+    /// while blocked, so there is nothing to "drain" for it here. A signal
+    /// that is *not* blocked either gets discarded immediately (disposition
+    /// `SIG_IGN`) or gets delivered: directly through
+    /// [`Self::deliver_to_userspace`] when the target is the thread running
+    /// this syscall (the common case — `kill`/`tkill` on yourself — and the
+    /// only case where building the frame here is safe, since it redirects
+    /// `self.tf`), or through the existing asynchronous `send_signal` path
+    /// for any other thread/process.
+    fn raise_and_send(&mut self, process: Arc<Mutex<Process>>, tid: isize, info: Siginfo) {
+        let signum = info.signo as usize;
+        let self_pid = self.process().pid;
+        // Checked without locking `thread_manager()` when the target is our
+        // own thread: `self.thread` is already our (non-reentrant) lock on
+        // it, so looking it up again here would deadlock.
+        let is_self_thread = tid >= 0 && tid as usize == self.thread.tid;
+        let is_self_process = tid < 0 && process.lock().pid == self_pid;
+        let blocked = if is_self_thread || is_self_process {
+            self.thread.sig_mask.contains(signum)
+        } else if tid >= 0 {
+            thread_manager()
+                .get(tid as usize)
+                .map(|thread| thread.lock().sig_mask.contains(signum))
+                .unwrap_or(false)
+        } else {
+            // We have no way to enumerate every thread in another process,
+            // so we can't tell whether all of them have `signum` blocked;
+            // treat it as deliverable rather than risk silently dropping it.
+            false
+        };
+        let ignored = process.lock().dispositions[signum].handler == SIG_IGN;
+        if ignored && !blocked {
+            return;
+        }
+        if blocked {
+            if is_self_thread {
+                self.thread.pending.raise(info);
+            } else if tid >= 0 {
+                if let Some(thread) = thread_manager().get(tid as usize) {
+                    thread.lock().pending.raise(info);
+                }
+            } else {
+                process.lock().pending.raise(info);
+            }
+            return;
+        }
+        if is_self_thread || is_self_process {
+            self.deliver_to_userspace(signum, info);
+        } else {
+            send_signal(process, tid, info);
+        }
+    }
+
+    /// Takes the lowest-numbered signal in `wanted` that is pending on
+    /// either the current thread or its process, checking both pools
+    /// before removing anything so a higher-numbered process-wide signal
+    /// can't jump ahead of a lower-numbered thread-directed one (or vice
+    /// versa).
+    fn take_lowest_pending(&mut self, wanted: &Sigset) -> Option<Siginfo> {
+        let thread_lowest = self.thread.pending.lowest(wanted);
+        let process_lowest = self.process().pending.lowest(wanted);
+        match (thread_lowest, process_lowest) {
+            (Some(t), Some(p)) if t <= p => self.thread.pending.take(wanted),
+            (Some(_), Some(_)) => self.process().pending.take(wanted),
+            (Some(_), None) => self.thread.pending.take(wanted),
+            (None, Some(_)) => self.process().pending.take(wanted),
+            (None, None) => None,
+        }
+    }
+
+    /// Checks the POSIX `kill` permission rule (see [`may_signal`]) for the
+    /// current process against `target`, returning `EPERM` if it fails.
+    ///
+    /// Takes `target` by `Arc` rather than an already-locked guard: it
+    /// reads the sender's own credentials (which may require locking this
+    /// same process, if `target` turns out to be self) before ever locking
+    /// `target`, so a self-targeted send (`tkill(gettid(), ...)`, a pidfd
+    /// opened on yourself, etc.) can't deadlock on a non-reentrant lock.
+    fn check_may_signal(&self, target: &Arc<Mutex<Process>>, signal: Signal) -> SysResult {
+        let (sender_ruid, sender_euid, sender_sid) = {
+            let sender = self.process();
+            (sender.ruid, sender.euid, sender.sid)
+        };
+        if may_signal(sender_ruid, sender_euid, sender_sid, signal, &target.lock()) {
+            Ok(0)
+        } else {
+            Err(EPERM)
+        }
+    }
+
+    /// Blocks until a signal in `set` becomes pending, then atomically
+    /// consumes it and reports it through `info`.
+    ///
+    /// Returns immediately if a matching signal is already pending.
+    /// Returns `EAGAIN` once `timeout` elapses, or `EINTR` if a signal
+    /// outside `set` that isn't blocked arrives first.
+    pub fn sys_rt_sigtimedwait(
+        &mut self,
+        set: *const Sigset,
+        info: *mut Siginfo,
+        timeout: *const TimeSpec,
+        sigsetsize: usize,
+    ) -> SysResult {
+        if sigsetsize != 8 {
+            return Err(EINVAL);
+        }
+        let set = unsafe { *self.vm().check_read_ptr(set)? };
+        let deadline = if timeout.is_null() {
+            None
+        } else {
+            let timeout = unsafe { *self.vm().check_read_ptr(timeout)? };
+            Some(timer_now() + timeout.to_duration())
+        };
+        info!("rt_sigtimedwait: set: {:?}, deadline: {:?}", set, deadline);
+
+        loop {
+            if let Some(sig) = self.take_lowest_pending(&set) {
+                if !info.is_null() {
+                    *unsafe { self.vm().check_write_ptr(info)? } = sig;
+                }
+                return Ok(sig.signo as usize);
+            }
+            let mask = self.thread.sig_mask;
+            if self.process().pending.contains_any_outside(&set, &mask)
+                || self.thread.pending.contains_any_outside(&set, &mask)
+            {
+                return Err(EINTR);
+            }
+            if matches!(deadline, Some(deadline) if timer_now() >= deadline) {
+                return Err(EAGAIN);
+            }
+            thread::yield_now();
+        }
+    }
+
     pub fn sys_rt_sigaction(
         &self,
         signum: usize,
@@ -45,12 +316,107 @@ impl Syscall<'_> {
         }
     }
 
+    /// If a pending signal is no longer blocked by the thread's current
+    /// `sig_mask`, takes and delivers the lowest-numbered one to user space.
+    /// At most one signal is delivered per call, since delivering redirects
+    /// `self.tf` to the handler and only one redirection can be in flight at
+    /// a time; any remaining unblocked signals wait for the next
+    /// opportunity (another `sys_rt_sigprocmask` call, or the handler
+    /// returning via `sys_rt_sigreturn`).
+    fn deliver_unblocked_signal(&mut self) {
+        let mut deliverable = Sigset::default();
+        for signum in 1..=64usize {
+            if !self.thread.sig_mask.contains(signum) {
+                deliverable.add(signum);
+            }
+        }
+        if let Some(info) = self.take_lowest_pending(&deliverable) {
+            self.deliver_to_userspace(info.signo as usize, info);
+        }
+    }
+
+    /// Builds the user-space signal-delivery frame for `signum` and
+    /// redirects `self.tf` to its handler, honoring `SA_SIGINFO` (the
+    /// handler is invoked with `(signo, *siginfo, ucontext)` rather than
+    /// just `(signo)`; the ucontext pointer is passed as null) and
+    /// `SA_ONSTACK` (the handler runs on the alternate signal stack, which
+    /// is marked `ONSTACK` for the duration). The thread's `sig_mask` and
+    /// the process's `sigaltstack`, saved onto the frame here,
+    /// are what `sys_rt_sigreturn` restores once the handler returns.
+    fn deliver_to_userspace(&mut self, signum: usize, info: Siginfo) {
+        const SA_SIGINFO: usize = 0x00000004;
+        const SA_ONSTACK: usize = 0x08000000;
+
+        let act = self.process().dispositions[signum];
+        let sigaltstack = self.process().sigaltstack;
+        let altstack_flags = SignalStackFlags::from_bits_truncate(sigaltstack.flags);
+        let use_altstack = act.flags & SA_ONSTACK != 0
+            && !altstack_flags.contains(SignalStackFlags::DISABLE)
+            && !altstack_flags.contains(SignalStackFlags::ONSTACK);
+
+        // FIXME: adapt arch — frame placement and the argument registers
+        // below mirror the teardown in `sys_rt_sigreturn`.
+        let sp = if use_altstack {
+            sigaltstack.sp + sigaltstack.size
+        } else {
+            self.tf.get_sp()
+        };
+        // The handler always enters with its stack pointer at `frame_addr`,
+        // as before SA_SIGINFO support existed (`sys_rt_sigreturn` locates
+        // the frame at `get_sp() - 8`), so returning from a handler is
+        // unaffected by whether this particular delivery carries a Siginfo.
+        // The Siginfo itself goes directly above the frame, between it and
+        // `sp`, never below the new stack pointer — the region below that is
+        // exactly where the handler's own stack frame grows into on entry,
+        // and would get clobbered before the handler ever reads it.
+        let siginfo_size = if act.flags & SA_SIGINFO != 0 {
+            core::mem::size_of::<Siginfo>()
+        } else {
+            0
+        };
+        let info_addr = sp - siginfo_size;
+        let frame_addr = info_addr - core::mem::size_of::<SignalFrame>() - 8;
+        let new_sp = frame_addr;
+        let frame = unsafe { &mut *(frame_addr as *mut SignalFrame) };
+        frame.tf = self.tf.clone();
+        frame.mask = self.thread.sig_mask;
+        frame.altstack = sigaltstack;
+
+        if use_altstack {
+            self.process().sigaltstack.flags |= SignalStackFlags::ONSTACK.bits();
+        }
+        self.thread.sig_mask.add_set(&act.mask);
+        self.thread.sig_mask.add(signum);
+
+        self.tf.rdi = signum;
+        if act.flags & SA_SIGINFO != 0 {
+            unsafe { *(info_addr as *mut Siginfo) = info };
+            self.tf.rsi = info_addr;
+            // ucontext construction (needed for handlers that inspect the
+            // interrupted machine state) isn't implemented; pass null.
+            self.tf.rdx = 0;
+        }
+        self.tf.set_sp(new_sp);
+        self.tf.set_ip(act.handler);
+    }
+
     pub fn sys_rt_sigreturn(&mut self) -> SysResult {
         info!("rt_sigreturn");
         // FIXME: adapt arch
         let frame = unsafe { &*((self.tf.get_sp() - 8) as *const SignalFrame) };
         *self.tf = frame.tf.clone();
+        // Undo what the `SA_SIGINFO`/`SA_ONSTACK` delivery path saved onto
+        // the frame before it redirected execution to the handler.
+        self.thread.sig_mask = frame.mask;
+        {
+            let sigaltstack = &mut self.process().sigaltstack;
+            *sigaltstack = frame.altstack;
+            sigaltstack.flags &= !SignalStackFlags::ONSTACK.bits();
+        }
         let ret = self.tf.rax as isize;
+        // The restored mask may unblock a signal that arrived while the
+        // handler ran.
+        self.deliver_unblocked_signal();
         if ret >= 0 {
             Ok(ret as usize)
         } else {
@@ -87,10 +453,37 @@ impl Syscall<'_> {
                 SETMASK => self.thread.sig_mask = *set,
                 _ => return Err(EINVAL),
             }
+            // Unblocking (or narrowing the mask via SETMASK) may make an
+            // already-pending signal deliverable; hand it to the handler
+            // now instead of leaving it parked until the next explicit
+            // sys_rt_sigtimedwait.
+            self.deliver_unblocked_signal();
         }
         return Ok(0);
     }
 
+    /// Reports the signals that are currently pending on this thread or its
+    /// process but blocked by the thread's `sig_mask`, as used by libc's
+    /// `sigpending`.
+    pub fn sys_rt_sigpending(&mut self, set: *mut Sigset, sigsetsize: usize) -> SysResult {
+        info!("rt_sigpending: set: {:?}, sigsetsize: {}", set, sigsetsize);
+        if sigsetsize != 8 {
+            return Err(EINVAL);
+        }
+        let mut union = self.process().pending.pending_set();
+        union.add_set(&self.thread.pending.pending_set());
+        let mask = self.thread.sig_mask;
+        let mut blocked = Sigset::default();
+        for signum in 1..=64usize {
+            if union.contains(signum) && mask.contains(signum) {
+                blocked.add(signum);
+            }
+        }
+        let set = unsafe { self.vm().check_write_ptr(set)? };
+        *set = blocked;
+        Ok(0)
+    }
+
     /// sending signal sig to process pid
     pub fn sys_kill(&mut self, pid: isize, signum: usize) -> SysResult {
         if let Some(signal) = <Signal as FromPrimitive>::from_usize(signum) {
@@ -101,42 +494,97 @@ impl Syscall<'_> {
                 code: SI_USER,
                 field: Default::default(),
             };
+            let (sender_ruid, sender_euid, sender_sid, sender_pgid) = {
+                let sender = self.process();
+                (sender.ruid, sender.euid, sender.sid, sender.pgid)
+            };
             match pid {
                 pid if pid > 0 => {
                     if let Some(process) = process(pid as usize) {
-                        send_signal(process, -1, info);
+                        let permitted = may_signal(
+                            sender_ruid,
+                            sender_euid,
+                            sender_sid,
+                            signal,
+                            &process.lock(),
+                        );
+                        if !permitted {
+                            return Err(EPERM);
+                        }
+                        self.raise_and_send(process, -1, info);
                         Ok(0)
                     } else {
                         Err(ESRCH)
                     }
                 }
                 0 => {
-                    let pgid = self.process().pgid;
-                    for process in process_group(pgid) {
-                        send_signal(process, -1, info);
+                    let mut sent = false;
+                    for process in process_group(sender_pgid) {
+                        let permitted = may_signal(
+                            sender_ruid,
+                            sender_euid,
+                            sender_sid,
+                            signal,
+                            &process.lock(),
+                        );
+                        if permitted {
+                            self.raise_and_send(process, -1, info);
+                            sent = true;
+                        }
+                    }
+                    if sent {
+                        Ok(0)
+                    } else {
+                        Err(EPERM)
                     }
-                    Ok(0)
                 }
                 -1 => {
-                    // TODO: check permissions
                     // sig is sent to every process for which the calling process
                     // has permission to send signals, except for process 1 (init)
+                    let mut sent = false;
                     for process in PROCESSES.read().values() {
                         if let Some(process) = process.upgrade() {
-                            send_signal(process, -1, info);
+                            let target = process.lock();
+                            if target.pid == 1 {
+                                continue;
+                            }
+                            if may_signal(sender_ruid, sender_euid, sender_sid, signal, &target) {
+                                drop(target);
+                                self.raise_and_send(process, -1, info);
+                                sent = true;
+                            }
                         }
                     }
-                    Ok(0)
+                    if sent {
+                        Ok(0)
+                    } else {
+                        Err(EPERM)
+                    }
                 }
                 _ => {
                     let process_group = process_group((-pid) as i32);
                     if process_group.is_empty() {
                         Err(ESRCH)
                     } else {
+                        let mut sent = false;
                         for process in process_group {
-                            send_signal(process, -1, info);
+                            let permitted = may_signal(
+                                sender_ruid,
+                                sender_euid,
+                                sender_sid,
+                                signal,
+                                &process.lock(),
+                            );
+                            if permitted {
+                                self.raise_and_send(process, -1, info);
+                                sent = true;
+                            }
+                        }
+                        if sent {
+                            Ok(0)
+                        } else {
+                            Err(EPERM)
                         }
-                        Ok(0)
                     }
                 }
             }
@@ -150,7 +598,8 @@ impl Syscall<'_> {
         if let Some(signal) = <Signal as FromPrimitive>::from_usize(signum) {
             info!("tkill: tid: {}, signal: {:?}", tid, signal);
             if let Some(process) = process_of(tid) {
-                send_signal(
+                self.check_may_signal(&process, signal)?;
+                self.raise_and_send(
                     process,
                     tid as isize,
                     Siginfo {
@@ -170,6 +619,62 @@ impl Syscall<'_> {
         }
     }
 
+    /// Sends `signum` to process `pid` together with a caller-supplied
+    /// `sigval` payload, marking the delivery as explicitly queued
+    /// (`SI_QUEUE`) rather than a plain `kill`.
+    pub fn sys_rt_sigqueueinfo(
+        &mut self,
+        pid: usize,
+        signum: usize,
+        info: *const Siginfo,
+    ) -> SysResult {
+        if let Some(signal) = <Signal as FromPrimitive>::from_usize(signum) {
+            let mut info = unsafe { *self.vm().check_read_ptr(info)? };
+            info.signo = signum as i32;
+            info.code = SI_QUEUE;
+            info!("rt_sigqueueinfo: pid: {}, signal: {:?}", pid, signal);
+            if let Some(process) = process(pid) {
+                self.check_may_signal(&process, signal)?;
+                self.raise_and_send(process, -1, info);
+                Ok(0)
+            } else {
+                Err(ESRCH)
+            }
+        } else {
+            Err(EINVAL)
+        }
+    }
+
+    /// Like [`Self::sys_rt_sigqueueinfo`] but targets a single thread
+    /// `tid` within thread group `tgid`, as used by `pthread_sigqueue`.
+    pub fn sys_rt_tgsigqueueinfo(
+        &mut self,
+        tgid: usize,
+        tid: usize,
+        signum: usize,
+        info: *const Siginfo,
+    ) -> SysResult {
+        if let Some(signal) = <Signal as FromPrimitive>::from_usize(signum) {
+            let mut info = unsafe { *self.vm().check_read_ptr(info)? };
+            info.signo = signum as i32;
+            info.code = SI_QUEUE;
+            info!(
+                "rt_tgsigqueueinfo: tgid: {}, tid: {}, signal: {:?}",
+                tgid, tid, signal
+            );
+            match process_of(tid) {
+                Some(process) if process.lock().pid == tgid => {
+                    self.check_may_signal(&process, signal)?;
+                    self.raise_and_send(process, tid as isize, info);
+                    Ok(0)
+                }
+                _ => Err(ESRCH),
+            }
+        } else {
+            Err(EINVAL)
+        }
+    }
+
     pub fn sys_sigaltstack(&self, ss: *const SignalStack, old_ss: *mut SignalStack) -> SysResult {
         const MINSIGSTKSZ: usize = 2048;
         if !old_ss.is_null() {
@@ -196,4 +701,135 @@ impl Syscall<'_> {
         }
         Ok(0)
     }
+
+    /// Opens a file-descriptor-like handle to process `pid`, so it can be
+    /// signaled later via [`Self::sys_pidfd_send_signal`] without racing a
+    /// pid that gets reused in between.
+    ///
+    /// The pidfd is installed in `self.process().files`, the process's own
+    /// [`FileTable`] — see that type's doc comment for the caveat that this
+    /// tree has no visible `open`/`read`/`write`/`close` fd subsystem for it
+    /// to plug into.
+    pub fn sys_pidfd_open(&mut self, pid: usize, flags: u32) -> SysResult {
+        info!("pidfd_open: pid: {}, flags: {}", pid, flags);
+        if flags != 0 {
+            return Err(EINVAL);
+        }
+        let process = process(pid).ok_or(ESRCH)?;
+        let pidfd = PidFd {
+            process: Arc::downgrade(&process),
+        };
+        Ok(self.process().files.add(Arc::new(pidfd)))
+    }
+
+    /// Sends `signum` to the process referenced by `pidfd`, reusing the
+    /// same enqueue path as `sys_kill`. Returns `ESRCH` if `pidfd` isn't a
+    /// pidfd or its target has since exited.
+    pub fn sys_pidfd_send_signal(
+        &mut self,
+        pidfd: usize,
+        signum: usize,
+        info: *const Siginfo,
+        flags: u32,
+    ) -> SysResult {
+        if flags != 0 {
+            return Err(EINVAL);
+        }
+        if let Some(signal) = <Signal as FromPrimitive>::from_usize(signum) {
+            info!("pidfd_send_signal: pidfd: {}, signal: {:?}", pidfd, signal);
+            let process = self
+                .process()
+                .files
+                .get(pidfd)
+                .and_then(|file| file.as_pidfd())
+                .and_then(|process| process.upgrade())
+                .ok_or(ESRCH)?;
+            let mut siginfo = if info.is_null() {
+                Siginfo {
+                    signo: signum as i32,
+                    errno: 0,
+                    code: SI_USER,
+                    field: Default::default(),
+                }
+            } else {
+                unsafe { *self.vm().check_read_ptr(info)? }
+            };
+            siginfo.signo = signum as i32;
+            self.check_may_signal(&process, signal)?;
+            self.raise_and_send(process, -1, siginfo);
+            Ok(0)
+        } else {
+            Err(EINVAL)
+        }
+    }
+}
+
+/// Minimal handle trait for objects installed in a process's
+/// file-descriptor table (`Process::files`). A pidfd only needs to be
+/// looked up and dropped; regular files/sockets would implement far more
+/// of this surface, wherever the rest of the fd subsystem lives.
+pub trait FileLike: Send + Sync {
+    /// Returns the referenced process if this file is a pidfd, so
+    /// `sys_pidfd_send_signal` can resolve it without a parallel table.
+    fn as_pidfd(&self) -> Option<Weak<Mutex<Process>>> {
+        None
+    }
+}
+
+/// A pidfd: a file-descriptor handle to `process`, so it can be signaled
+/// later without racing a pid that gets reused in between.
+struct PidFd {
+    process: Weak<Mutex<Process>>,
+}
+
+impl FileLike for PidFd {
+    fn as_pidfd(&self) -> Option<Weak<Mutex<Process>>> {
+        Some(self.process.clone())
+    }
+}
+
+/// A process's open file-descriptor table (`Process::files`), handing out
+/// fd numbers the same way for every kind of [`FileLike`] entry.
+///
+/// This module only implements the signal-related syscalls, and this
+/// snapshot of the tree has no `open`/`read`/`write`/`close` syscalls (or
+/// any other fd abstraction) for `FileLike`/`FileTable` to integrate with —
+/// there is nothing else here to confirm `Process::files` against, so this
+/// is recorded as an open question for whoever owns that subsystem rather
+/// than silently assumed away. `next_fd` starts past the conventional
+/// stdio range (0/1/2) as a defensive measure against the most likely
+/// collision, but a real fd subsystem's own counter (inherited across
+/// `fork`, reused after `close`, etc.) should replace this entirely rather
+/// than run alongside it.
+pub struct FileTable {
+    entries: BTreeMap<usize, Arc<dyn FileLike>>,
+    next_fd: usize,
+}
+
+impl Default for FileTable {
+    fn default() -> Self {
+        FileTable {
+            entries: BTreeMap::new(),
+            next_fd: 3,
+        }
+    }
+}
+
+impl FileTable {
+    /// Installs `file` at the next free fd and returns that fd.
+    pub fn add(&mut self, file: Arc<dyn FileLike>) -> usize {
+        let fd = self.next_fd;
+        self.next_fd += 1;
+        self.entries.insert(fd, file);
+        fd
+    }
+
+    pub fn get(&self, fd: usize) -> Option<Arc<dyn FileLike>> {
+        self.entries.get(&fd).cloned()
+    }
+
+    /// Closes `fd`, returning the entry that was there, if any.
+    pub fn remove(&mut self, fd: usize) -> Option<Arc<dyn FileLike>> {
+        self.entries.remove(&fd)
+    }
 }